@@ -1,15 +1,20 @@
+use std::num::NonZeroUsize;
+
 use typst_utils::{Get, Numeric};
 
 use crate::diag::{bail, SourceResult};
 use crate::engine::Engine;
 use crate::foundations::{
-    cast, elem, scope, Array, Content, NativeElement, Packed, Show, Smart, StyleChain,
-    Styles, TargetElem,
+    cast, elem, scope, Array, Content, IntoValue, NativeElement, Packed, Show, Smart,
+    StyleChain, Styles, TargetElem,
+};
+use crate::html::{attr, tag, HtmlAttrs, HtmlElem};
+use crate::layout::{
+    Em, GridElem, HElem, Length, Sides, Sizing, StackChild, StackElem, TrackSizings,
+    VElem,
 };
-use crate::html::{tag, HtmlElem};
-use crate::layout::{Em, HElem, Length, Sides, StackChild, StackElem, VElem};
-use crate::model::{ListItemLike, ListLike, ParElem, ParbreakElem};
-use crate::text::TextElem;
+use crate::model::{ListItemLike, ListLike, Numbering, ParElem, ParbreakElem};
+use crate::text::{LinebreakElem, TextElem};
 
 /// A list of terms and their descriptions.
 ///
@@ -54,6 +59,51 @@ pub struct TermsElem {
     #[default(true)]
     pub tight: bool,
 
+    /// How to lay out the terms and their descriptions.
+    ///
+    /// - `{"hanging"}`: Each item forms its own block, with the description
+    ///   wrapping underneath the term at a hanging indent.
+    /// - `{"tabular"}`: Terms and descriptions are aligned into two shared
+    ///   columns, like a glossary table, with the description column
+    ///   starting right after the widest term. Here, `indent` is the gap
+    ///   between the two columns; `separator` and `hanging-indent` are not
+    ///   used.
+    /// - `{"run-in"}`: Term and description flow together as a single
+    ///   paragraph, with successive items packed onto the same line until it
+    ///   fills up. Useful for compact glossaries or index-style entries.
+    ///   `{tight}` has no effect on this layout, since descriptions never
+    ///   start their own paragraph here. Items are separated by `item-gap`
+    ///   instead of `spacing`, and neither `indent` nor `hanging-indent` is
+    ///   applied.
+    ///
+    /// ```example
+    /// #set terms(layout: "tabular")
+    /// / Short: A description.
+    /// / Longer term: Another description.
+    /// ```
+    #[default(TermsLayout::Hanging)]
+    pub layout: TermsLayout,
+
+    /// How to number the terms.
+    ///
+    /// By default, terms are not numbered. Set this to a
+    /// [numbering pattern]($numbering) such as `{"1."}` or `{"a)"}` to turn
+    /// the term list into a numbered glossary; the counter resets for every
+    /// distinct term list.
+    ///
+    /// ```example
+    /// #set terms(numbering: "1.")
+    /// / Alpha: The first letter.
+    /// / Beta: The second letter.
+    /// ```
+    #[default(Smart::Auto)]
+    pub numbering: Smart<Option<Numbering>>,
+
+    /// The gap between a term's marker and the term itself, when
+    /// `numbering` is set.
+    #[default(HElem::new(Em::new(0.3).into()).with_weak(true).pack())]
+    pub marker_gap: Content,
+
     /// The separator between the item and the description.
     ///
     /// If you want to just separate them with a certain amount of space, use
@@ -68,6 +118,12 @@ pub struct TermsElem {
     #[default(HElem::new(Em::new(0.6).into()).with_weak(true).pack())]
     pub separator: Content,
 
+    /// The gap between successive items when `layout` is `{"run-in"}`.
+    ///
+    /// Has no effect for the other layouts.
+    #[default(HElem::new(Em::new(1.0).into()).with_weak(true).pack())]
+    pub item_gap: Content,
+
     /// The indentation of each item.
     pub indent: Length,
 
@@ -118,38 +174,86 @@ impl TermsElem {
 }
 
 impl Show for Packed<TermsElem> {
-    fn show(&self, _: &mut Engine, styles: StyleChain) -> SourceResult<Content> {
+    fn show(&self, engine: &mut Engine, styles: StyleChain) -> SourceResult<Content> {
         let span = self.span();
         let tight = self.tight.get(styles);
+        let layout = self.layout.get(styles);
+
+        // Markers reset for every distinct term list: they are derived from
+        // this element's own children, not from a document-wide counter.
+        let numbering = self.numbering.get_ref(styles).clone().custom().flatten();
+        let mut markers = Vec::with_capacity(self.children.len());
+        for i in 0..self.children.len() {
+            markers.push(match &numbering {
+                Some(numbering) => {
+                    let n = NonZeroUsize::new(i + 1).unwrap();
+                    Some(numbering.apply(engine, &[n])?)
+                }
+                None => None,
+            });
+        }
 
         if styles.get(TargetElem::target).is_html() {
-            return Ok(HtmlElem::new(tag::dl)
-                .with_body(Some(Content::sequence(self.children.iter().flat_map(
-                    |item| {
-                        // Text in wide term lists shall always turn into paragraphs.
-                        let mut description = item.description.clone();
-                        if !tight {
-                            description += ParbreakElem::shared();
-                        }
-
-                        [
+            let mut dl = HtmlElem::new(tag::dl).with_body(Some(Content::sequence(
+                self.children.iter().zip(&markers).flat_map(|(item, marker)| {
+                    // Text in wide term lists shall always turn into
+                    // paragraphs, except in run-in mode, where a paragraph
+                    // break would split the single packed flow back apart.
+                    let mut description = item.description.clone();
+                    if !tight && layout != TermsLayout::RunIn {
+                        description += ParbreakElem::shared();
+                    }
+
+                    let mut seq: Vec<Content> = item
+                        .term
+                        .iter()
+                        .enumerate()
+                        .map(|(i, term)| {
+                            let mut body = term.clone();
+                            // Carry the number as a leading span on the first
+                            // `dt` of the item.
+                            if i == 0 {
+                                if let Some(marker) = marker {
+                                    body = HtmlElem::new(tag::span)
+                                        .with_body(Some(marker.clone()))
+                                        .pack()
+                                        .spanned(term.span())
+                                        + body;
+                                }
+                            }
                             HtmlElem::new(tag::dt)
-                                .with_body(Some(item.term.clone()))
+                                .with_body(Some(body))
                                 .pack()
-                                .spanned(item.term.span()),
-                            HtmlElem::new(tag::dd)
-                                .with_body(Some(description))
-                                .pack()
-                                .spanned(item.description.span()),
-                        ]
-                    },
-                ))))
-                .pack());
+                                .spanned(term.span())
+                        })
+                        .collect();
+                    seq.push(
+                        HtmlElem::new(tag::dd)
+                            .with_body(Some(description))
+                            .pack()
+                            .spanned(item.description.span()),
+                    );
+                    seq
+                }),
+            )));
+
+            if layout == TermsLayout::RunIn {
+                dl = dl.with_attrs(HtmlAttrs::new([(attr::class, "terms-run-in".into())]));
+            }
+
+            return Ok(dl.pack());
         }
 
         let separator = self.separator.get_ref(styles);
-        let indent = self.indent.get(styles);
         let hanging_indent = self.hanging_indent.get(styles);
+
+        // A term list nested inside another term's description is already
+        // rendered inside that description's own `.padded()`/column inset
+        // (for a hanging or tabular parent), so it inherits the enclosing
+        // indent purely through containment. Each level only needs to apply
+        // its own indent on top of that. A run-in parent adds no inset.
+        let indent = self.indent.get(styles);
+
         let gutter = self.spacing.get(styles).unwrap_or_else(|| {
             if tight {
                 styles.get(ParElem::leading)
@@ -158,15 +262,118 @@ impl Show for Packed<TermsElem> {
             }
         });
 
+        if layout == TermsLayout::Tabular {
+            let marker_gap = self.marker_gap.get_ref(styles);
+            let mut cells = Vec::with_capacity(2 * self.children.len());
+            for (child, marker) in self.children.iter().zip(&markers) {
+                let mut terms = vec![];
+                if let Some(marker) = marker {
+                    terms.push(marker.clone());
+                    terms.push((*marker_gap).clone());
+                }
+                for (i, term) in child.term.iter().enumerate() {
+                    if i > 0 {
+                        terms.push(LinebreakElem::shared().clone());
+                    }
+                    terms.push(term.clone().strong());
+                }
+                cells.push(Content::sequence(terms));
+
+                // Text in wide term lists shall always turn into paragraphs.
+                let mut description = child.description.clone();
+                if !tight {
+                    description += ParbreakElem::shared();
+                }
+                cells.push(description);
+            }
+
+            return Ok(GridElem::new(cells)
+                .with_columns(TrackSizings(
+                    [Sizing::Auto, Sizing::Fr(1.0.into())].into(),
+                ))
+                .with_column_gutter(TrackSizings([indent.into()].into()))
+                .with_row_gutter(TrackSizings([gutter.into()].into()))
+                .pack()
+                .spanned(span)
+                .set(TermsElem::within, true));
+        }
+
+        if layout == TermsLayout::RunIn {
+            // Unlike the other layouts, run-in descriptions never turn into
+            // their own paragraph, even when `tight` is `{false}`: the whole
+            // point of this layout is to pack every item into one flow, and
+            // a paragraph break would split that flow right back apart.
+            let item_gap = self.item_gap.get_ref(styles);
+            let marker_gap = self.marker_gap.get_ref(styles);
+            let mut seq = vec![];
+            for (i, (child, marker)) in
+                self.children.iter().zip(&markers).enumerate()
+            {
+                if i > 0 {
+                    seq.push((*item_gap).clone());
+                }
+                if let Some(marker) = marker {
+                    seq.push(marker.clone());
+                    seq.push((*marker_gap).clone());
+                }
+                for (j, term) in child.term.iter().enumerate() {
+                    if j > 0 {
+                        seq.push(TextElem::packed(", "));
+                    }
+                    seq.push(term.clone().strong());
+                }
+                seq.push((*separator).clone());
+                seq.push(child.description.clone());
+            }
+
+            return Ok(Content::sequence(seq).spanned(span).set(TermsElem::within, true));
+        }
+
         let pad = hanging_indent + indent;
         let unpad = (!hanging_indent.is_zero())
             .then(|| HElem::new((-hanging_indent).into()).pack().spanned(span));
 
+        let marker_gap = self.marker_gap.get_ref(styles);
         let mut children = vec![];
-        for child in self.children.iter() {
-            let mut seq = vec![];
-            seq.extend(unpad.clone());
-            seq.push(child.term.clone().strong());
+        for (child, marker) in self.children.iter().zip(&markers) {
+            // The marker labels the first term, matching the HTML and
+            // run-in code paths. Middle terms (beyond the first, excluding
+            // the last) stack above the shared, hanging-indented
+            // description, each at the same outdent as the description's
+            // first line.
+            let Some((first_term, rest_terms)) = child.term.split_first() else {
+                bail!(child.span(), "term item must have at least one term");
+            };
+
+            let mut first_seq = vec![];
+            first_seq.extend(unpad.clone());
+            if let Some(marker) = marker {
+                first_seq.push(marker.clone());
+                first_seq.push((*marker_gap).clone());
+            }
+            first_seq.push(first_term.clone().strong());
+
+            let mut seq = match rest_terms.split_last() {
+                None => {
+                    // Only one term: it shares the description's line.
+                    first_seq
+                }
+                Some((last_term, middle_terms)) => {
+                    children.push(StackChild::Block(Content::sequence(first_seq)));
+                    for term in middle_terms {
+                        let mut seq = vec![];
+                        seq.extend(unpad.clone());
+                        seq.push(term.clone().strong());
+                        children.push(StackChild::Block(Content::sequence(seq)));
+                    }
+
+                    let mut seq = vec![];
+                    seq.extend(unpad.clone());
+                    seq.push(last_term.clone().strong());
+                    seq
+                }
+            };
+
             seq.push((*separator).clone());
             seq.push(child.description.clone());
 
@@ -205,12 +412,42 @@ impl Show for Packed<TermsElem> {
     }
 }
 
+/// How the terms and descriptions of a [`TermsElem`] are laid out.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum TermsLayout {
+    /// Each item is its own block, with the description hanging below the
+    /// term.
+    Hanging,
+    /// Terms and descriptions are aligned into two columns.
+    Tabular,
+    /// Term and description flow together as a single paragraph.
+    RunIn,
+}
+
+cast! {
+    TermsLayout,
+    self => match self {
+        Self::Hanging => "hanging".into_value(),
+        Self::Tabular => "tabular".into_value(),
+        Self::RunIn => "run-in".into_value(),
+    },
+    "hanging" => Self::Hanging,
+    "tabular" => Self::Tabular,
+    "run-in" => Self::RunIn,
+}
+
 /// A term list item.
 #[elem(name = "item", title = "Term List Item")]
 pub struct TermItem {
     /// The term described by the list item.
+    ///
+    /// An array of terms that all share the `description`, mirroring how an
+    /// HTML `<dl>` allows multiple `<dt>` elements before a single `<dd>`.
+    /// When an item is given as a `(term, description)` array, the term can
+    /// also be a single piece of content; when calling `terms.item`
+    /// directly, it must be an array.
     #[required]
-    pub term: Content,
+    pub term: Vec<Content>,
 
     /// The description of the term.
     #[required]
@@ -222,9 +459,19 @@ cast! {
     array: Array => {
         let mut iter = array.into_iter();
         let (term, description) = match (iter.next(), iter.next(), iter.next()) {
-            (Some(a), Some(b), None) => (a.cast()?, b.cast()?),
+            (Some(a), Some(b), None) => (a, b.cast()?),
             _ => bail!("array must contain exactly two entries"),
         };
+        let term = match term.clone().cast::<Array>() {
+            Ok(terms) => terms
+                .into_iter()
+                .map(|term| term.cast())
+                .collect::<Result<Vec<Content>, _>>()?,
+            Err(_) => vec![term.cast()?],
+        };
+        if term.is_empty() {
+            bail!("term item must have at least one term");
+        }
         Self::new(term, description)
     },
     v: Content => v.unpack::<Self>().map_err(|_| "expected term item or array")?,
@@ -240,7 +487,9 @@ impl ListLike for TermsElem {
 
 impl ListItemLike for TermItem {
     fn styled(mut item: Packed<Self>, styles: Styles) -> Packed<Self> {
-        item.term.style_in_place(styles.clone());
+        for term in item.term.iter_mut() {
+            term.style_in_place(styles.clone());
+        }
         item.description.style_in_place(styles);
         item
     }